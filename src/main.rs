@@ -6,21 +6,71 @@ struct FieldSize {
     size: i32,
 }
 
+#[derive(Resource)]
+struct MoveTimer(Timer);
+
+#[derive(Resource)]
+struct GameSettings {
+    base_interval: f32,
+    min_interval: f32,
+    speedup_factor: f32,
+}
+
 #[derive(Component)]
 struct Food {
     position: Position,
 }
 
+#[derive(Clone, Copy, PartialEq)]
 struct Position {
     x: i32,
     y: i32,
 }
 
+#[derive(Event)]
+struct GrowthEvent;
+
+#[derive(Event)]
+struct GameOverEvent;
+
+#[derive(States, Debug, Clone, Copy, Eq, PartialEq, Hash, Default)]
+enum GameState {
+    #[default]
+    MainMenu,
+    Playing,
+    GameOver,
+}
+
+#[derive(Component)]
+struct GridText;
+
+#[derive(Component)]
+struct MainMenuItem;
+
+#[derive(Component)]
+struct Focused;
+
+#[derive(Component, Clone, Copy, PartialEq)]
+enum MenuAction {
+    Start,
+    FieldSize,
+    Quit,
+}
+
+#[derive(Component, Clone, Copy)]
+struct MenuLayout {
+    position: Vec2,
+}
+
+#[derive(Resource, Default)]
+struct LastTailPosition(Option<Position>);
+
 #[derive(Component)]
 struct Snake {
     position: Vec<Position>,
     length: i32,
     direction: KeyCode,
+    next_direction: KeyCode,
 }
 
 fn initialize_snake(mut commands: Commands) {
@@ -28,23 +78,26 @@ fn initialize_snake(mut commands: Commands) {
         position: vec![Position { x: 0, y: 0 }],
         length: 1,
         direction: KeyCode::Comma,
+        next_direction: KeyCode::Comma,
     });
 }
 
 fn initialize_food(mut commands: Commands, field: Res<FieldSize>) {
+    let position = random_food_position(&field, &[Position { x: 0, y: 0 }]);
+    commands.spawn(Food { position });
+}
+
+/// Rejection-samples a cell inside the field that isn't in `occupied`.
+fn random_food_position(field: &FieldSize, occupied: &[Position]) -> Position {
     let mut rng = rand::rng();
     let half_size = field.size / 2;
     loop {
-        let random_x = rng.random_range(-half_size..half_size + 1);
-        let random_y = rng.random_range(-half_size..half_size + 1);
-        if random_x != 0 || random_y != 0 {
-            commands.spawn(Food {
-                position: Position {
-                    x: random_x,
-                    y: random_y,
-                },
-            });
-            break;
+        let candidate = Position {
+            x: rng.random_range(-half_size..half_size + 1),
+            y: rng.random_range(-half_size..half_size + 1),
+        };
+        if !occupied.contains(&candidate) {
+            return candidate;
         }
     }
 }
@@ -83,61 +136,270 @@ fn setup_ui(
         // 6) Background color if you want the Node to have a background
         //    (optional)
         BackgroundColor(Color::NONE),
+        GridText,
     ));
 }
 
+/// Recomputes the grid font size after `FieldSize` changes in the main menu.
+fn update_grid_font_size(
+    field: Res<FieldSize>,
+    window: Query<&Window>,
+    mut text_font_query: Query<&mut TextFont, With<GridText>>,
+) {
+    let Ok(window) = window.get_single() else {
+        return;
+    };
+    let font_size = window.resolution.height() / field.size as f32;
+    if let Ok(mut text_font) = text_font_query.get_single_mut() {
+        text_font.font_size = font_size;
+    }
+}
+
+fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>, field: Res<FieldSize>) {
+    let entries = [
+        (MenuAction::Start, "Start".to_string()),
+        (MenuAction::FieldSize, format!("Field Size: {}", field.size)),
+        (MenuAction::Quit, "Quit".to_string()),
+    ];
+
+    for (index, (action, label)) in entries.into_iter().enumerate() {
+        let mut entity = commands.spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(40.0 + index as f32 * 8.0),
+                left: Val::Percent(50.0),
+                margin: UiRect::all(Val::Auto),
+                ..Default::default()
+            },
+            Text(label),
+            TextFont {
+                font: asset_server.load("LiberationMono-Regular.ttf"),
+                font_size: 32.0,
+                font_smoothing: bevy::text::FontSmoothing::None,
+            },
+            TextColor(Color::WHITE),
+            action,
+            MenuLayout {
+                position: Vec2::new(0.0, index as f32),
+            },
+            MainMenuItem,
+        ));
+
+        if index == 0 {
+            entity.insert(Focused);
+        }
+    }
+}
+
+fn teardown_main_menu(mut commands: Commands, menu_query: Query<Entity, With<MainMenuItem>>) {
+    for entity in &menu_query {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Moves the `Focused` marker to the nearest menu item in the requested direction,
+/// driven by either arrow keys or a gamepad D-pad/stick.
+fn menu_focus_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    focused_query: Query<(Entity, &MenuLayout), With<Focused>>,
+    items_query: Query<(Entity, &MenuLayout), Without<Focused>>,
+) {
+    let mut direction = Vec2::ZERO;
+    if keyboard_input.just_pressed(KeyCode::ArrowUp) {
+        direction.y -= 1.0;
+    }
+    if keyboard_input.just_pressed(KeyCode::ArrowDown) {
+        direction.y += 1.0;
+    }
+    for gamepad in &gamepads {
+        if gamepad.just_pressed(GamepadButton::DPadUp) {
+            direction.y -= 1.0;
+        }
+        if gamepad.just_pressed(GamepadButton::DPadDown) {
+            direction.y += 1.0;
+        }
+        let stick_y = gamepad.get(GamepadAxis::LeftStickY).unwrap_or(0.0);
+        if stick_y > 0.5 {
+            direction.y -= 1.0;
+        } else if stick_y < -0.5 {
+            direction.y += 1.0;
+        }
+    }
+
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    let Ok((current_entity, current_layout)) = focused_query.get_single() else {
+        return;
+    };
+
+    let mut nearest: Option<(Entity, f32)> = None;
+    for (entity, layout) in &items_query {
+        let offset = layout.position - current_layout.position;
+        if offset.dot(direction) <= 0.0 {
+            continue;
+        }
+        let distance = offset.length();
+        if nearest.map_or(true, |(_, nearest_distance)| distance < nearest_distance) {
+            nearest = Some((entity, distance));
+        }
+    }
+
+    if let Some((next_entity, _)) = nearest {
+        commands.entity(current_entity).remove::<Focused>();
+        commands.entity(next_entity).insert(Focused);
+    }
+}
+
+/// Activates the focused menu item (Enter / gamepad South) and lets the
+/// Field Size entry be adjusted left/right before Start is pressed.
+fn menu_action_system(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut field: ResMut<FieldSize>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit_events: EventWriter<AppExit>,
+    focused_query: Query<&MenuAction, With<Focused>>,
+    mut text_query: Query<(&MenuAction, &mut Text)>,
+) {
+    let Ok(action) = focused_query.get_single() else {
+        return;
+    };
+
+    if *action == MenuAction::FieldSize {
+        let mut delta = 0;
+        if keyboard_input.just_pressed(KeyCode::ArrowRight) {
+            delta += 2;
+        }
+        if keyboard_input.just_pressed(KeyCode::ArrowLeft) {
+            delta -= 2;
+        }
+        for gamepad in &gamepads {
+            if gamepad.just_pressed(GamepadButton::DPadRight) {
+                delta += 2;
+            }
+            if gamepad.just_pressed(GamepadButton::DPadLeft) {
+                delta -= 2;
+            }
+        }
+        if delta != 0 {
+            field.size = (field.size + delta).clamp(10, 50);
+            for (action, mut text) in &mut text_query {
+                if *action == MenuAction::FieldSize {
+                    text.0 = format!("Field Size: {}", field.size);
+                }
+            }
+        }
+    }
+
+    let activated = keyboard_input.just_pressed(KeyCode::Enter)
+        || gamepads
+            .iter()
+            .any(|gamepad| gamepad.just_pressed(GamepadButton::South));
+
+    if activated {
+        match action {
+            MenuAction::Start => next_state.set(GameState::Playing),
+            MenuAction::FieldSize => {}
+            MenuAction::Quit => exit_events.send(AppExit::Success),
+        };
+    }
+}
+
 fn main() {
+    let game_settings = GameSettings {
+        base_interval: 0.2,
+        min_interval: 0.02,
+        speedup_factor: 0.95,
+    };
+
     App::new()
         .add_plugins(DefaultPlugins)
         .insert_resource(FieldSize { size: 30 })
+        .insert_resource(MoveTimer(Timer::from_seconds(
+            game_settings.base_interval,
+            TimerMode::Repeating,
+        )))
+        .insert_resource(game_settings)
+        .insert_resource(LastTailPosition::default())
+        .init_state::<GameState>()
+        .add_event::<GrowthEvent>()
+        .add_event::<GameOverEvent>()
+        .add_systems(Startup, (setup_ui, setup_camera))
+        .add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
+        .add_systems(OnExit(GameState::MainMenu), teardown_main_menu)
+        .add_systems(
+            Update,
+            (menu_focus_system, menu_action_system).run_if(in_state(GameState::MainMenu)),
+        )
         .add_systems(
-            Startup,
-            (initialize_snake, initialize_food, setup_ui, setup_camera),
+            OnEnter(GameState::Playing),
+            (initialize_snake, initialize_food, update_grid_font_size),
+        )
+        .add_systems(
+            Update,
+            (
+                snake_input_system,
+                snake_movement_system,
+                check_eating,
+                grow_snake,
+                check_collisions,
+                handle_game_over,
+            )
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(Update, render_system.run_if(in_state(GameState::Playing)))
+        .add_systems(
+            Update,
+            (game_over_render_system, restart_system).run_if(in_state(GameState::GameOver)),
         )
-        .add_systems(Update, snake_movement_system)
-        .add_systems(Update, render_system)
         .run();
 }
 
+fn snake_input_system(keyboard_input: Res<ButtonInput<KeyCode>>, mut query: Query<&mut Snake>) {
+    if let Ok(mut snake) = query.get_single_mut() {
+        // Buffer the requested turn; the movement step applies it on the next tick.
+        // Validate against the already-buffered direction so a second turn queued
+        // within the same tick is judged against what the snake is about to do.
+        if keyboard_input.just_pressed(KeyCode::KeyW)
+            && direction_check(snake.next_direction, KeyCode::KeyW)
+        {
+            snake.next_direction = KeyCode::KeyW;
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyS)
+            && direction_check(snake.next_direction, KeyCode::KeyS)
+        {
+            snake.next_direction = KeyCode::KeyS;
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyA)
+            && direction_check(snake.next_direction, KeyCode::KeyA)
+        {
+            snake.next_direction = KeyCode::KeyA;
+        }
+        if keyboard_input.just_pressed(KeyCode::KeyD)
+            && direction_check(snake.next_direction, KeyCode::KeyD)
+        {
+            snake.next_direction = KeyCode::KeyD;
+        }
+    }
+}
+
 fn snake_movement_system(
-    keyboard_input: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut timer: ResMut<MoveTimer>,
     mut query: Query<&mut Snake>,
-    field: Res<FieldSize>,
+    mut last_tail: ResMut<LastTailPosition>,
 ) {
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
     if let Ok(mut snake) = query.get_single_mut() {
-        // Move the snake based on keyboard input
-        if keyboard_input.just_pressed(KeyCode::KeyW) {
-            let legitimacy = direction_check(snake.direction, KeyCode::KeyW);
-            println!("{:?}", legitimacy);
-            println!("Moving up!");
-            if legitimacy {
-                snake.direction = KeyCode::KeyW
-            };
-        }
-        if keyboard_input.just_pressed(KeyCode::KeyS) {
-            let legitimacy = direction_check(snake.direction, KeyCode::KeyS);
-            println!("{:?}", legitimacy);
-            println!("Moving down!");
-            if legitimacy {
-                snake.direction = KeyCode::KeyS
-            };
-        }
-        if keyboard_input.just_pressed(KeyCode::KeyA) {
-            let legitimacy = direction_check(snake.direction, KeyCode::KeyA);
-            println!("{:?}", legitimacy);
-            println!("Moving left!");
-            if legitimacy {
-                snake.direction = KeyCode::KeyA
-            };
-        }
-        if keyboard_input.just_pressed(KeyCode::KeyD) {
-            let legitimacy = direction_check(snake.direction, KeyCode::KeyD);
-            println!("{:?}", legitimacy);
-            println!("Moving right!");
-            if legitimacy {
-                snake.direction = KeyCode::KeyD
-            };
-        }
+        snake.direction = snake.next_direction;
 
         let head = &snake.position[0];
         let new_head = match snake.direction {
@@ -162,12 +424,133 @@ fn snake_movement_system(
                 y: head.y,
             },
         };
-        std::thread::sleep(std::time::Duration::from_millis(250));
 
         snake.position.insert(0, new_head);
-        if snake.position.len() > snake.length as usize {
-            snake.position.pop();
+        last_tail.0 = snake.position.pop();
+    }
+}
+
+fn check_eating(
+    mut commands: Commands,
+    snake_query: Query<&Snake>,
+    food_query: Query<(Entity, &Food)>,
+    field: Res<FieldSize>,
+    mut growth_events: EventWriter<GrowthEvent>,
+) {
+    let Ok(snake) = snake_query.get_single() else {
+        return;
+    };
+    let head = snake.position[0];
+
+    for (entity, food) in &food_query {
+        if food.position == head {
+            commands.entity(entity).despawn();
+            growth_events.send(GrowthEvent);
+
+            let position = random_food_position(&field, &snake.position);
+            commands.spawn(Food { position });
+        }
+    }
+}
+
+fn grow_snake(
+    mut growth_events: EventReader<GrowthEvent>,
+    mut query: Query<&mut Snake>,
+    mut last_tail: ResMut<LastTailPosition>,
+    mut timer: ResMut<MoveTimer>,
+    settings: Res<GameSettings>,
+) {
+    if let Ok(mut snake) = query.get_single_mut() {
+        for _ in growth_events.read() {
+            snake.length += 1;
+            // Re-append the cell the tail just vacated so growth lands exactly there.
+            if let Some(tail) = last_tail.0.take() {
+                snake.position.push(tail);
+            }
+
+            let next_interval = (timer.0.duration().as_secs_f32() * settings.speedup_factor)
+                .max(settings.min_interval);
+            timer
+                .0
+                .set_duration(std::time::Duration::from_secs_f32(next_interval));
+        }
+    }
+}
+
+fn check_collisions(
+    snake_query: Query<&Snake>,
+    field: Res<FieldSize>,
+    mut game_over_events: EventWriter<GameOverEvent>,
+) {
+    let Ok(snake) = snake_query.get_single() else {
+        return;
+    };
+    let half = field.size / 2;
+    let head = snake.position[0];
+
+    let out_of_bounds = head.x < -half || head.x > half || head.y < -half || head.y > half;
+    let self_collision = snake.position[1..].contains(&head);
+
+    if out_of_bounds || self_collision {
+        game_over_events.send(GameOverEvent);
+    }
+}
+
+/// Reacts to `GameOverEvent` by freezing gameplay and switching to the game-over screen.
+fn handle_game_over(
+    mut game_over_events: EventReader<GameOverEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if game_over_events.read().next().is_some() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+fn game_over_render_system(
+    field: Res<FieldSize>,
+    snake_query: Query<&Snake>,
+    mut text_query: Query<&mut Text>,
+) {
+    let size = field.size as usize;
+    let length = snake_query
+        .get_single()
+        .map(|snake| snake.length)
+        .unwrap_or_default();
+    let message = format!("GAME OVER - length {length} - press R");
+    let pad = size.saturating_sub(message.len()) / 2;
+
+    let mut grid_string = String::new();
+    for row in 0..size {
+        if row == size / 2 {
+            grid_string.push_str(&" ".repeat(pad));
+            grid_string.push_str(&message);
+        }
+        grid_string.push('\n');
+    }
+
+    if let Ok(mut text) = text_query.get_single_mut() {
+        text.0 = grid_string;
+    }
+}
+
+fn restart_system(
+    mut commands: Commands,
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    snake_query: Query<Entity, With<Snake>>,
+    food_query: Query<Entity, With<Food>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut timer: ResMut<MoveTimer>,
+    settings: Res<GameSettings>,
+) {
+    if keyboard_input.just_pressed(KeyCode::KeyR) {
+        for entity in &snake_query {
+            commands.entity(entity).despawn();
+        }
+        for entity in &food_query {
+            commands.entity(entity).despawn();
         }
+        timer.0 = Timer::from_seconds(settings.base_interval, TimerMode::Repeating);
+        next_state.set(GameState::Playing);
     }
 }
 